@@ -1,14 +1,17 @@
 use std::fmt;
 use types::{SlackResult, ErrHexColor};
-use rustc_serialize::hex::FromHex;
-use rustc_serialize::json::{ToJson, Json};
-use rustc_serialize::{Encodable, Encoder};
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+use serde::de::Error as DeError;
+// `hex` is renamed to `hex_crate` in Cargo.toml to avoid clashing with this module's own name
+use hex_crate::decode as hex_decode;
 
 /// The `HexColor` string can be one of:
 ///
 /// 1. `good`, `warning`, `danger`
 /// 2. The built-in enums: `SlackColor::Good`, etc.
-/// 3. Any valid hex color code: `#b13d41`
+/// 3. Any valid hex color code: `#b13d41` (shorthand like `#b34` is also accepted)
+/// 4. A CSS functional color: `rgb(177, 61, 65)`, `hsla(357, 49%, 47%, 0.5)`, etc.
+/// 5. A standard X11/CSS color name: `rebeccapurple`, `steelblue`, etc.
 /// hex color codes will be checked to ensure a valid hex number is provided
 pub struct HexColor(String);
 
@@ -78,15 +81,20 @@ impl HexColorT for HexColor {
     }
 }
 
-impl ToJson for HexColor {
-    fn to_json(&self) -> Json {
-        Json::String(format!("{:?}", &self))
+impl Serialize for HexColor {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.serialize_str(&format!("{:?}", &self))
     }
 }
 
-impl Encodable for HexColor {
-    fn encode<S: Encoder>(&self, encoder: &mut S) -> Result<(), S::Error> {
-        encoder.emit_str(format!("{:?}", &self).as_ref())
+impl<'de> Deserialize<'de> for HexColor {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        let s = String::deserialize(deserializer)?;
+        s.as_str().into_hex_color().map_err(DeError::custom)
     }
 }
 
@@ -97,26 +105,328 @@ trait IntoHexColor {
     fn into_hex_color(self) -> SlackResult<HexColor>;
 }
 
+/// Standard X11/CSS named colors (e.g. `"rebeccapurple"`, `"steelblue"`), mapping the
+/// name to its canonical `#rrggbb` form. Consulted before the hex-format checks in
+/// `into_hex_color` so callers can use familiar color names alongside raw hex and the
+/// Slack-specific `good`/`warning`/`danger` keywords.
+const NAMED_COLORS: [(&'static str, &'static str); 148] = [
+    ("aliceblue", "#f0f8ff"),
+    ("antiquewhite", "#faebd7"),
+    ("aqua", "#00ffff"),
+    ("aquamarine", "#7fffd4"),
+    ("azure", "#f0ffff"),
+    ("beige", "#f5f5dc"),
+    ("bisque", "#ffe4c4"),
+    ("black", "#000000"),
+    ("blanchedalmond", "#ffebcd"),
+    ("blue", "#0000ff"),
+    ("blueviolet", "#8a2be2"),
+    ("brown", "#a52a2a"),
+    ("burlywood", "#deb887"),
+    ("cadetblue", "#5f9ea0"),
+    ("chartreuse", "#7fff00"),
+    ("chocolate", "#d2691e"),
+    ("coral", "#ff7f50"),
+    ("cornflowerblue", "#6495ed"),
+    ("cornsilk", "#fff8dc"),
+    ("crimson", "#dc143c"),
+    ("cyan", "#00ffff"),
+    ("darkblue", "#00008b"),
+    ("darkcyan", "#008b8b"),
+    ("darkgoldenrod", "#b8860b"),
+    ("darkgray", "#a9a9a9"),
+    ("darkgreen", "#006400"),
+    ("darkgrey", "#a9a9a9"),
+    ("darkkhaki", "#bdb76b"),
+    ("darkmagenta", "#8b008b"),
+    ("darkolivegreen", "#556b2f"),
+    ("darkorange", "#ff8c00"),
+    ("darkorchid", "#9932cc"),
+    ("darkred", "#8b0000"),
+    ("darksalmon", "#e9967a"),
+    ("darkseagreen", "#8fbc8f"),
+    ("darkslateblue", "#483d8b"),
+    ("darkslategray", "#2f4f4f"),
+    ("darkslategrey", "#2f4f4f"),
+    ("darkturquoise", "#00ced1"),
+    ("darkviolet", "#9400d3"),
+    ("deeppink", "#ff1493"),
+    ("deepskyblue", "#00bfff"),
+    ("dimgray", "#696969"),
+    ("dimgrey", "#696969"),
+    ("dodgerblue", "#1e90ff"),
+    ("firebrick", "#b22222"),
+    ("floralwhite", "#fffaf0"),
+    ("forestgreen", "#228b22"),
+    ("fuchsia", "#ff00ff"),
+    ("gainsboro", "#dcdcdc"),
+    ("ghostwhite", "#f8f8ff"),
+    ("gold", "#ffd700"),
+    ("goldenrod", "#daa520"),
+    ("gray", "#808080"),
+    ("green", "#008000"),
+    ("greenyellow", "#adff2f"),
+    ("grey", "#808080"),
+    ("honeydew", "#f0fff0"),
+    ("hotpink", "#ff69b4"),
+    ("indianred", "#cd5c5c"),
+    ("indigo", "#4b0082"),
+    ("ivory", "#fffff0"),
+    ("khaki", "#f0e68c"),
+    ("lavender", "#e6e6fa"),
+    ("lavenderblush", "#fff0f5"),
+    ("lawngreen", "#7cfc00"),
+    ("lemonchiffon", "#fffacd"),
+    ("lightblue", "#add8e6"),
+    ("lightcoral", "#f08080"),
+    ("lightcyan", "#e0ffff"),
+    ("lightgoldenrodyellow", "#fafad2"),
+    ("lightgray", "#d3d3d3"),
+    ("lightgreen", "#90ee90"),
+    ("lightgrey", "#d3d3d3"),
+    ("lightpink", "#ffb6c1"),
+    ("lightsalmon", "#ffa07a"),
+    ("lightseagreen", "#20b2aa"),
+    ("lightskyblue", "#87cefa"),
+    ("lightslategray", "#778899"),
+    ("lightslategrey", "#778899"),
+    ("lightsteelblue", "#b0c4de"),
+    ("lightyellow", "#ffffe0"),
+    ("lime", "#00ff00"),
+    ("limegreen", "#32cd32"),
+    ("linen", "#faf0e6"),
+    ("magenta", "#ff00ff"),
+    ("maroon", "#800000"),
+    ("mediumaquamarine", "#66cdaa"),
+    ("mediumblue", "#0000cd"),
+    ("mediumorchid", "#ba55d3"),
+    ("mediumpurple", "#9370db"),
+    ("mediumseagreen", "#3cb371"),
+    ("mediumslateblue", "#7b68ee"),
+    ("mediumspringgreen", "#00fa9a"),
+    ("mediumturquoise", "#48d1cc"),
+    ("mediumvioletred", "#c71585"),
+    ("midnightblue", "#191970"),
+    ("mintcream", "#f5fffa"),
+    ("mistyrose", "#ffe4e1"),
+    ("moccasin", "#ffe4b5"),
+    ("navajowhite", "#ffdead"),
+    ("navy", "#000080"),
+    ("oldlace", "#fdf5e6"),
+    ("olive", "#808000"),
+    ("olivedrab", "#6b8e23"),
+    ("orange", "#ffa500"),
+    ("orangered", "#ff4500"),
+    ("orchid", "#da70d6"),
+    ("palegoldenrod", "#eee8aa"),
+    ("palegreen", "#98fb98"),
+    ("paleturquoise", "#afeeee"),
+    ("palevioletred", "#db7093"),
+    ("papayawhip", "#ffefd5"),
+    ("peachpuff", "#ffdab9"),
+    ("peru", "#cd853f"),
+    ("pink", "#ffc0cb"),
+    ("plum", "#dda0dd"),
+    ("powderblue", "#b0e0e6"),
+    ("purple", "#800080"),
+    ("rebeccapurple", "#663399"),
+    ("red", "#ff0000"),
+    ("rosybrown", "#bc8f8f"),
+    ("royalblue", "#4169e1"),
+    ("saddlebrown", "#8b4513"),
+    ("salmon", "#fa8072"),
+    ("sandybrown", "#f4a460"),
+    ("seagreen", "#2e8b57"),
+    ("seashell", "#fff5ee"),
+    ("sienna", "#a0522d"),
+    ("silver", "#c0c0c0"),
+    ("skyblue", "#87ceeb"),
+    ("slateblue", "#6a5acd"),
+    ("slategray", "#708090"),
+    ("slategrey", "#708090"),
+    ("snow", "#fffafa"),
+    ("springgreen", "#00ff7f"),
+    ("steelblue", "#4682b4"),
+    ("tan", "#d2b48c"),
+    ("teal", "#008080"),
+    ("thistle", "#d8bfd8"),
+    ("tomato", "#ff6347"),
+    ("turquoise", "#40e0d0"),
+    ("violet", "#ee82ee"),
+    ("wheat", "#f5deb3"),
+    ("white", "#ffffff"),
+    ("whitesmoke", "#f5f5f5"),
+    ("yellow", "#ffff00"),
+    ("yellowgreen", "#9acd32"),
+];
+
 impl<'a> IntoHexColor for &'a str {
     /// Attempt to convert a &str into a `HexColor`
     fn into_hex_color(self) -> SlackResult<HexColor> {
         if SLACK_COLORS.contains(&self) {
             return Ok(HexColor(self.to_owned()));
         }
-        if self.chars().count() != 7 {
+        if let Some(color) = try!(parse_functional_color(self)) {
+            return Ok(color);
+        }
+        if let Some(&(_, hex)) = NAMED_COLORS.iter().find(|&&(name, _)| name == self) {
+            return Ok(HexColor(hex.to_owned()));
+        }
+        if self.chars().next() != Some('#') {
+            if self.chars().count() == 7 {
+                return fail!((ErrHexColor, "No leading #"));
+            }
             return fail!((ErrHexColor, "Must be 7 characters long (including #)"));
         }
-        if self.chars().next().unwrap() != '#' {
-            return fail!((ErrHexColor, "No leading #"));
+        // shorthand form: `#` plus 3 hex digits, e.g. `#b34`
+        if self.chars().count() == 4 {
+            let expanded: String = self[1..].chars().flat_map(|c| vec![c, c]).collect();
+            return match hex_decode(&expanded) {
+                Ok(_) => Ok(HexColor(self.to_owned())),
+                Err(e) => fail!(e),
+            };
+        }
+        if self.chars().count() != 7 {
+            return fail!((ErrHexColor, "Must be 4 or 7 characters long (including #)"));
         }
         // see if the remaining part of the string is actually hex
-        match self[1..].from_hex() {
+        match hex_decode(&self[1..]) {
             Ok(_) => Ok(HexColor(self.to_owned())),
             Err(e) => fail!(e),
         }
     }
 }
 
+/// Recognize CSS functional color notation (`rgb()`, `rgba()`, `hsl()`, `hsla()`) and
+/// convert it to a canonical `#rrggbb` `HexColor`. Returns `Ok(None)` when `s` doesn't
+/// look like one of these forms, so callers can fall through to the plain hex parsing.
+fn parse_functional_color(s: &str) -> SlackResult<Option<HexColor>> {
+    let (body, is_hsl) = if s.starts_with("rgba(") && s.ends_with(')') {
+        (&s[5..s.len() - 1], false)
+    } else if s.starts_with("rgb(") && s.ends_with(')') {
+        (&s[4..s.len() - 1], false)
+    } else if s.starts_with("hsla(") && s.ends_with(')') {
+        (&s[5..s.len() - 1], true)
+    } else if s.starts_with("hsl(") && s.ends_with(')') {
+        (&s[4..s.len() - 1], true)
+    } else {
+        return Ok(None);
+    };
+
+    let parts: Vec<&str> = body.split(',').map(|p| p.trim()).collect();
+    if parts.len() < 3 {
+        return fail!((ErrHexColor, "Expected at least 3 color components"));
+    }
+    // optional alpha channel is parsed (to catch malformed input) and then discarded
+    if parts.len() > 3 {
+        if parts[3].parse::<f64>().is_err() {
+            return fail!((ErrHexColor, "Invalid alpha component"));
+        }
+    }
+
+    let (r, g, b) = if is_hsl {
+        let h = match parts[0].parse::<f64>() {
+            Ok(h) => h,
+            Err(_) => return fail!((ErrHexColor, "Invalid hue component")),
+        };
+        let s = try!(parse_percentage(parts[1]));
+        let l = try!(parse_percentage(parts[2]));
+        hsl_to_rgb(h, s, l)
+    } else {
+        (try!(parse_u8_channel(parts[0])),
+         try!(parse_u8_channel(parts[1])),
+         try!(parse_u8_channel(parts[2])))
+    };
+
+    Ok(Some(HexColor(format!("#{:02x}{:02x}{:02x}", r, g, b))))
+}
+
+/// Parse a single `rgb()`/`rgba()` channel (`0`-`255`)
+fn parse_u8_channel(s: &str) -> SlackResult<u8> {
+    match s.parse::<u8>() {
+        Ok(v) => Ok(v),
+        Err(_) => fail!((ErrHexColor, "Invalid color channel, expected 0-255")),
+    }
+}
+
+/// Parse an `hsl()`/`hsla()` percentage component (`saturation`/`lightness`), clamped to 0-100
+fn parse_percentage(s: &str) -> SlackResult<f64> {
+    match s.trim_end_matches('%').parse::<f64>() {
+        Ok(v) => Ok(v.max(0.0).min(100.0)),
+        Err(_) => fail!((ErrHexColor, "Invalid percentage component")),
+    }
+}
+
+/// Convert HSL (hue in degrees, saturation/lightness as 0-100) to RGB channels
+fn hsl_to_rgb(h: f64, s: f64, l: f64) -> (u8, u8, u8) {
+    let h = ((h % 360.0) + 360.0) % 360.0;
+    let s = s / 100.0;
+    let l = l / 100.0;
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let x = c * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
+    let m = l - c / 2.0;
+    let (r1, g1, b1) = match h {
+        h if h < 60.0 => (c, x, 0.0),
+        h if h < 120.0 => (x, c, 0.0),
+        h if h < 180.0 => (0.0, c, x),
+        h if h < 240.0 => (0.0, x, c),
+        h if h < 300.0 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    (((r1 + m) * 255.0).round() as u8,
+     ((g1 + m) * 255.0).round() as u8,
+     ((b1 + m) * 255.0).round() as u8)
+}
+
+impl Default for HexColor {
+    /// Black (`#000`), used as a sensible fallback color for attachment builders
+    fn default() -> HexColor {
+        HexColor("#000".to_owned())
+    }
+}
+
+impl HexColor {
+    /// Build a `HexColor` from RGB channels, e.g. for colors computed from a metric value
+    pub fn from_rgb(r: u8, g: u8, b: u8) -> HexColor {
+        HexColor(format!("#{:02x}{:02x}{:02x}", r, g, b))
+    }
+
+    /// Red channel, 0-255
+    pub fn r(&self) -> u8 {
+        self.rgb()[0]
+    }
+
+    /// Green channel, 0-255
+    pub fn g(&self) -> u8 {
+        self.rgb()[1]
+    }
+
+    /// Blue channel, 0-255
+    pub fn b(&self) -> u8 {
+        self.rgb()[2]
+    }
+
+    /// Parse the stored hex string back into RGB channels, expanding shorthand first.
+    /// Falls back to `[0, 0, 0]` for non-hex values like the `good`/`warning`/`danger`
+    /// keywords, which have no RGB representation.
+    fn rgb(&self) -> [u8; 3] {
+        let HexColor(ref text) = *self;
+        if text.chars().next() != Some('#') {
+            return [0, 0, 0];
+        }
+        let body = &text[1..];
+        let expanded: String = if body.chars().count() == 3 {
+            body.chars().flat_map(|c| vec![c, c]).collect()
+        } else {
+            body.to_owned()
+        };
+        match hex_decode(&expanded) {
+            Ok(bytes) => [bytes[0], bytes[1], bytes[2]],
+            Err(_) => [0, 0, 0],
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use hex::*;
@@ -184,4 +494,134 @@ mod test {
         let h = h1.unwrap();
         assert_eq!(format!("{:?}", h), "#103d18".to_owned());
     }
+
+    #[test]
+    fn test_hex_color_valid_shorthand() {
+        let h1: SlackResult<HexColor> = HexColorT::new("#b34");
+        let h = h1.unwrap();
+        assert_eq!(format!("{:?}", h), "#b34".to_owned());
+    }
+
+    #[test]
+    fn test_hex_color_invalid_shorthand() {
+        let h1: SlackResult<HexColor> = HexColorT::new("#b3z");
+        let h = h1.unwrap_err();
+        assert_eq!(h.desc, "Invalid character 'z' at position 4".to_owned());
+    }
+
+    #[test]
+    fn test_hex_color_default() {
+        let h: HexColor = Default::default();
+        assert_eq!(format!("{:?}", h), "#000".to_owned());
+    }
+
+    #[test]
+    fn test_hex_color_rgb() {
+        let h1: SlackResult<HexColor> = HexColorT::new("rgb(177, 61, 65)");
+        let h = h1.unwrap();
+        assert_eq!(format!("{:?}", h), "#b13d41".to_owned());
+    }
+
+    #[test]
+    fn test_hex_color_rgba_ignores_alpha() {
+        let h1: SlackResult<HexColor> = HexColorT::new("rgba(177, 61, 65, 0.5)");
+        let h = h1.unwrap();
+        assert_eq!(format!("{:?}", h), "#b13d41".to_owned());
+    }
+
+    #[test]
+    fn test_hex_color_hsl() {
+        let h1: SlackResult<HexColor> = HexColorT::new("hsl(357, 49%, 47%)");
+        let h = h1.unwrap();
+        assert_eq!(format!("{:?}", h), "#b33d43".to_owned());
+    }
+
+    #[test]
+    fn test_hex_color_hsla_ignores_alpha() {
+        let h1: SlackResult<HexColor> = HexColorT::new("hsla(357, 49%, 47%, 0.5)");
+        let h = h1.unwrap();
+        assert_eq!(format!("{:?}", h), "#b33d43".to_owned());
+    }
+
+    #[test]
+    fn test_hex_color_rgb_invalid_channel() {
+        let h1: SlackResult<HexColor> = HexColorT::new("rgb(300, 61, 65)");
+        let h = h1.unwrap_err();
+        assert_eq!(h.desc, "Invalid color channel, expected 0-255".to_owned());
+    }
+
+    #[test]
+    fn test_hex_color_hsl_invalid_hue() {
+        let h1: SlackResult<HexColor> = HexColorT::new("hsl(abc, 10%, 10%)");
+        let h = h1.unwrap_err();
+        assert_eq!(h.desc, "Invalid hue component".to_owned());
+    }
+
+    #[test]
+    fn test_hex_color_hsl_invalid_percentage() {
+        let h1: SlackResult<HexColor> = HexColorT::new("hsl(10, abc, 10%)");
+        let h = h1.unwrap_err();
+        assert_eq!(h.desc, "Invalid percentage component".to_owned());
+    }
+
+    #[test]
+    fn test_hex_color_functional_too_few_components() {
+        let h1: SlackResult<HexColor> = HexColorT::new("rgb(10, 20)");
+        let h = h1.unwrap_err();
+        assert_eq!(h.desc, "Expected at least 3 color components".to_owned());
+    }
+
+    #[test]
+    fn test_hex_color_functional_invalid_alpha() {
+        let h1: SlackResult<HexColor> = HexColorT::new("rgba(10, 20, 30, abc)");
+        let h = h1.unwrap_err();
+        assert_eq!(h.desc, "Invalid alpha component".to_owned());
+    }
+
+    #[test]
+    fn test_hex_color_named() {
+        let h1: SlackResult<HexColor> = HexColorT::new("rebeccapurple");
+        let h = h1.unwrap();
+        assert_eq!(format!("{:?}", h), "#663399".to_owned());
+    }
+
+    #[test]
+    fn test_hex_color_named_unknown() {
+        let h1: SlackResult<HexColor> = HexColorT::new("notacolor");
+        let h = h1.unwrap_err();
+        assert_eq!(h.desc, "Must be 7 characters long (including #)".to_owned());
+    }
+
+    #[test]
+    fn test_hex_color_from_rgb() {
+        let h = HexColor::from_rgb(177, 61, 65);
+        assert_eq!(format!("{:?}", h), "#b13d41".to_owned());
+    }
+
+    #[test]
+    fn test_hex_color_rgb_accessors() {
+        let h = HexColor::from_rgb(177, 61, 65);
+        assert_eq!(h.r(), 177);
+        assert_eq!(h.g(), 61);
+        assert_eq!(h.b(), 65);
+    }
+
+    #[test]
+    fn test_hex_color_rgb_accessors_shorthand() {
+        let h1: SlackResult<HexColor> = HexColorT::new("#b34");
+        let h = h1.unwrap();
+        assert_eq!(h.r(), 0xbb);
+        assert_eq!(h.g(), 0x33);
+        assert_eq!(h.b(), 0x44);
+    }
+
+    #[test]
+    fn test_hex_color_rgb_accessors_keyword_fallback() {
+        // `HexColor`s built from the `good`/`warning`/`danger` keywords have no RGB
+        // representation, so the accessors fall back to black rather than erroring.
+        let h: HexColor = HexColorT::new(&SlackColor::Danger);
+        assert_eq!(h.r(), 0);
+        assert_eq!(h.g(), 0);
+        assert_eq!(h.b(), 0);
+    }
 }